@@ -0,0 +1,111 @@
+use std::process::ExitStatus;
+
+use crate::command::Command;
+use crate::util::units::Second;
+
+use super::executor::Executor;
+use super::timing_result::TimingResult;
+
+use anyhow::Result;
+
+/// Parameters that control when the adaptive run loop stops collecting
+/// samples for a given command.
+#[derive(Debug, Clone, Copy)]
+pub struct AdaptiveRunsOptions {
+    /// Stop once `stderr / mean` drops below this fraction, e.g. 0.01 for 1%.
+    pub target_relative_stderr: f64,
+    pub min_runs: u64,
+    pub max_runs: u64,
+}
+
+/// Tracks count, mean and squared-distance-from-mean (`M2`) of a sample
+/// stream using Welford's online algorithm, so the variance can be derived
+/// without keeping every sample in memory.
+#[derive(Debug, Default)]
+struct WelfordAccumulator {
+    count: u64,
+    mean: Second,
+    m2: Second,
+}
+
+impl WelfordAccumulator {
+    fn push(&mut self, x: Second) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / (self.count as Second);
+        self.m2 += delta * (x - self.mean);
+    }
+
+    fn variance(&self) -> Option<Second> {
+        if self.count > 1 {
+            Some(self.m2 / ((self.count - 1) as Second))
+        } else {
+            None
+        }
+    }
+
+    /// The standard error of the mean, `sqrt(variance / n)`.
+    fn standard_error(&self) -> Option<Second> {
+        self.variance().map(|var| (var / (self.count as Second)).sqrt())
+    }
+
+    /// The standard error relative to the mean, used as the stopping
+    /// criterion for the adaptive run count.
+    fn relative_standard_error(&self) -> Option<Second> {
+        if self.mean > 0.0 {
+            self.standard_error().map(|stderr| stderr / self.mean)
+        } else {
+            None
+        }
+    }
+}
+
+/// Run `command` with `executor` until the mean has stabilized according to
+/// `options`, or `options.max_runs` is reached. Returns the collected
+/// (overhead-adjusted) timing results and exit statuses, one pair per run.
+pub fn run_adaptive(
+    executor: &dyn Executor,
+    command: &Command<'_>,
+    options: AdaptiveRunsOptions,
+) -> Result<Vec<(TimingResult, ExitStatus)>> {
+    let mut results = Vec::new();
+    let mut accumulator = WelfordAccumulator::default();
+
+    loop {
+        let (mut timing_result, status) = executor.run_command_and_measure(command, None)?;
+        timing_result.time_real = (timing_result.time_real - executor.time_overhead()).max(0.0);
+
+        accumulator.push(timing_result.time_real);
+        results.push((timing_result, status));
+
+        let reached_min_runs = accumulator.count >= options.min_runs;
+        let reached_max_runs = accumulator.count >= options.max_runs;
+        let is_stable = accumulator
+            .relative_standard_error()
+            .map(|rel_stderr| rel_stderr < options.target_relative_stderr)
+            .unwrap_or(false);
+
+        if reached_max_runs || (reached_min_runs && is_stable) {
+            break;
+        }
+    }
+
+    Ok(results)
+}
+
+#[test]
+fn test_welford_accumulator_matches_naive_variance() {
+    let samples = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+
+    let mut accumulator = WelfordAccumulator::default();
+    for &x in &samples {
+        accumulator.push(x);
+    }
+
+    let naive_mean = samples.iter().sum::<f64>() / samples.len() as f64;
+    let naive_variance = samples.iter().map(|x| (x - naive_mean).powi(2)).sum::<f64>()
+        / (samples.len() - 1) as f64;
+
+    assert!((accumulator.mean - naive_mean).abs() < 1e-9);
+    assert!((accumulator.variance().unwrap() - naive_variance).abs() < 1e-9);
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use super::timing_result::TimingResult;
+use crate::util::units::Second;
+
+use statistical::{mean, median, standard_deviation};
+
+/// The aggregated statistics for a single benchmarked command.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkResult {
+    /// The full command line, as entered by the user
+    pub command: String,
+
+    /// The mean run time
+    pub mean: Second,
+
+    /// The standard deviation of all run times. Not available if only a single run has been
+    /// performed.
+    pub stddev: Option<Second>,
+
+    /// The median run time
+    pub median: Second,
+
+    /// Time spent in user mode
+    pub user: Second,
+
+    /// Time spent in kernel mode
+    pub system: Second,
+
+    /// Min run time
+    pub min: Second,
+
+    /// Max run time
+    pub max: Second,
+
+    /// All run times
+    pub times: Option<Vec<Second>>,
+
+    /// Exit codes of all runs
+    pub exit_codes: Vec<Option<i32>>,
+
+    /// Parameter values for this benchmark
+    pub parameters: BTreeMap<String, String>,
+
+    /// The peak resident set size observed across all runs, in bytes. `None`
+    /// if no run reported a non-zero value (e.g. the executor doesn't
+    /// support memory measurement).
+    pub memory_peak: Option<u64>,
+}
+
+/// The peak memory usage across a set of per-run timing results, or `None`
+/// if none of the runs reported a non-zero peak.
+fn aggregate_memory_peak(timing_results: &[TimingResult]) -> Option<u64> {
+    timing_results.iter().map(|r| r.memory_peak).max().filter(|&peak| peak > 0)
+}
+
+impl BenchmarkResult {
+    /// Build a [`BenchmarkResult`] from the raw per-run timing results collected for a command.
+    pub fn from_timing_results(
+        command: String,
+        timing_results: &[TimingResult],
+        exit_codes: Vec<Option<i32>>,
+        parameters: BTreeMap<String, String>,
+    ) -> Self {
+        let times: Vec<Second> = timing_results.iter().map(|r| r.time_real).collect();
+        let user_times: Vec<Second> = timing_results.iter().map(|r| r.time_user).collect();
+        let system_times: Vec<Second> = timing_results.iter().map(|r| r.time_system).collect();
+
+        let stddev = if times.len() > 1 {
+            Some(standard_deviation(&times, None))
+        } else {
+            None
+        };
+
+        BenchmarkResult {
+            command,
+            mean: mean(&times),
+            stddev,
+            median: median(&times),
+            user: mean(&user_times),
+            system: mean(&system_times),
+            min: times.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: times.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            times: Some(times),
+            exit_codes,
+            parameters,
+            memory_peak: aggregate_memory_peak(timing_results),
+        }
+    }
+}
+
+#[test]
+fn test_aggregate_memory_peak_picks_the_max() {
+    let make = |memory_peak| TimingResult {
+        time_real: 0.0,
+        time_user: 0.0,
+        time_system: 0.0,
+        memory_peak,
+    };
+
+    let timing_results = vec![make(100), make(300), make(200)];
+    assert_eq!(aggregate_memory_peak(&timing_results), Some(300));
+}
+
+#[test]
+fn test_aggregate_memory_peak_is_none_when_unsupported() {
+    let make = |memory_peak| TimingResult {
+        time_real: 0.0,
+        time_user: 0.0,
+        time_system: 0.0,
+        memory_peak,
+    };
+
+    let timing_results = vec![make(0), make(0)];
+    assert_eq!(aggregate_memory_peak(&timing_results), None);
+}
@@ -92,6 +92,7 @@ impl<'a> Executor for RawExecutor<'a> {
                 time_real: result.time_real,
                 time_user: result.time_user,
                 time_system: result.time_system,
+                memory_peak: result.memory_peak,
             },
             result.status,
         ))
@@ -152,6 +153,7 @@ impl<'a> Executor for ShellExecutor<'a> {
                 time_real: result.time_real,
                 time_user: result.time_user,
                 time_system: result.time_system,
+                memory_peak: result.memory_peak,
             },
             result.status,
         ))
@@ -211,6 +213,7 @@ impl<'a> Executor for ShellExecutor<'a> {
             time_real: mean(&times_real),
             time_user: mean(&times_user),
             time_system: mean(&times_system),
+            memory_peak: 0,
         });
 
         Ok(())
@@ -264,6 +267,7 @@ impl Executor for MockExecutor {
                 time_real: Self::extract_time(command.get_command_line()),
                 time_user: 0.0,
                 time_system: 0.0,
+                memory_peak: 0,
             },
             status,
         ))
@@ -0,0 +1,136 @@
+pub mod adaptive;
+pub mod benchmark_result;
+pub mod executor;
+pub mod scheduler;
+pub mod timing_result;
+
+use std::collections::BTreeMap;
+use std::process::ExitStatus;
+
+use crate::command::Command;
+use crate::options::Options;
+
+use adaptive::AdaptiveRunsOptions;
+use benchmark_result::BenchmarkResult;
+use executor::Executor;
+use timing_result::TimingResult;
+
+use anyhow::Result;
+
+/// Run all of `commands` against `executor`.
+///
+/// If `options.shuffle_seed` is set, the runs are interleaved across
+/// commands in a shuffled order (see [`scheduler`]) instead of running all
+/// of one command's repetitions before moving to the next. Interleaving is
+/// only compatible with a fixed run count: if `options.adaptive_runs` is
+/// also set, a warning is printed and the adaptive stopping rule is ignored
+/// in favor of the fixed `options.runs` count.
+pub fn run(
+    commands: &[Command<'_>],
+    executor: &dyn Executor,
+    options: &Options,
+) -> Result<Vec<BenchmarkResult>> {
+    if let Some(seed) = options.shuffle_seed {
+        if options.adaptive_runs.is_some() {
+            eprintln!(
+                "Warning: --adaptive is not supported together with --shuffle-seed; \
+                 running a fixed {} times per command instead.",
+                options.runs
+            );
+        }
+
+        run_interleaved(commands, executor, options, seed)
+    } else {
+        commands
+            .iter()
+            .map(|command| run_command(command, executor, options))
+            .collect()
+    }
+}
+
+fn run_interleaved(
+    commands: &[Command<'_>],
+    executor: &dyn Executor,
+    options: &Options,
+    seed: u64,
+) -> Result<Vec<BenchmarkResult>> {
+    eprintln!("Shuffling commands with seed {}", seed);
+
+    let schedule = scheduler::interleaved_schedule(commands.len(), options.runs, seed);
+    let mut runs: Vec<Vec<(TimingResult, ExitStatus)>> = vec![Vec::new(); commands.len()];
+
+    for round in schedule {
+        for command_index in round {
+            let (mut timing_result, status) =
+                executor.run_command_and_measure(&commands[command_index], None)?;
+            timing_result.time_real =
+                (timing_result.time_real - executor.time_overhead()).max(0.0);
+            runs[command_index].push((timing_result, status));
+        }
+    }
+
+    commands
+        .iter()
+        .zip(runs)
+        .map(|(command, command_runs)| benchmark_result_from_runs(command, command_runs))
+        .collect()
+}
+
+fn run_command(
+    command: &Command<'_>,
+    executor: &dyn Executor,
+    options: &Options,
+) -> Result<BenchmarkResult> {
+    let runs: Vec<(TimingResult, ExitStatus)> = if let Some(adaptive_options) =
+        options.adaptive_runs
+    {
+        adaptive::run_adaptive(executor, command, adaptive_options)?
+    } else {
+        run_fixed(executor, command, options.runs)?
+    };
+
+    benchmark_result_from_runs(command, runs)
+}
+
+fn run_fixed(
+    executor: &dyn Executor,
+    command: &Command<'_>,
+    runs: u64,
+) -> Result<Vec<(TimingResult, ExitStatus)>> {
+    (0..runs)
+        .map(|_| {
+            let (mut timing_result, status) = executor.run_command_and_measure(command, None)?;
+            timing_result.time_real =
+                (timing_result.time_real - executor.time_overhead()).max(0.0);
+            Ok((timing_result, status))
+        })
+        .collect()
+}
+
+fn benchmark_result_from_runs(
+    command: &Command<'_>,
+    runs: Vec<(TimingResult, ExitStatus)>,
+) -> Result<BenchmarkResult> {
+    let timing_results: Vec<TimingResult> = runs.iter().map(|(timing, _)| *timing).collect();
+    let exit_codes = runs.iter().map(|(_, status)| status.code()).collect();
+
+    Ok(BenchmarkResult::from_timing_results(
+        command.get_command_line(),
+        &timing_results,
+        exit_codes,
+        BTreeMap::new(),
+    ))
+}
+
+/// Default adaptive-run parameters used when `--adaptive` is given without
+/// overriding `--min-runs`/`--max-runs`.
+pub const DEFAULT_MIN_RUNS: u64 = 10;
+pub const DEFAULT_MAX_RUNS: u64 = 1000;
+
+pub fn adaptive_options_from(target_relative_stderr: f64, min_runs: u64, max_runs: u64) -> AdaptiveRunsOptions {
+    AdaptiveRunsOptions {
+        target_relative_stderr,
+        min_runs,
+        max_runs,
+    }
+}
@@ -0,0 +1,94 @@
+/// A seedable xorshift/SplitMix64 generator, used to shuffle the execution
+/// order of commands without pulling in an external RNG dependency.
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// A uniformly distributed integer in `0..bound`.
+    fn next_below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % (bound as u64)) as usize
+    }
+}
+
+/// Produce a random seed suitable for [`SplitMix64::new`], so that a run can
+/// print the seed it picked and let the user reproduce it later via
+/// `--shuffle-seed`.
+pub fn random_seed() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Shuffle `indices` in place using the Fisher-Yates algorithm.
+pub fn shuffle(indices: &mut [usize], rng: &mut SplitMix64) {
+    for i in (1..indices.len()).rev() {
+        let j = rng.next_below(i + 1);
+        indices.swap(i, j);
+    }
+}
+
+/// Build the per-round execution order for `num_commands` commands,
+/// interleaving them across `rounds` so that systematic bias (thermal
+/// drift, cache warmup, background load) is spread evenly across all
+/// commands instead of concentrated on whichever command runs first.
+pub fn interleaved_schedule(num_commands: usize, rounds: u64, seed: u64) -> Vec<Vec<usize>> {
+    let mut rng = SplitMix64::new(seed);
+    let mut schedule = Vec::with_capacity(rounds as usize);
+
+    for _ in 0..rounds {
+        let mut order: Vec<usize> = (0..num_commands).collect();
+        shuffle(&mut order, &mut rng);
+        schedule.push(order);
+    }
+
+    schedule
+}
+
+#[test]
+fn test_shuffle_is_a_permutation() {
+    let mut rng = SplitMix64::new(42);
+    let mut indices: Vec<usize> = (0..10).collect();
+    shuffle(&mut indices, &mut rng);
+
+    let mut sorted = indices.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, (0..10).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_interleaved_schedule_visits_each_command_once_per_round() {
+    let schedule = interleaved_schedule(4, 5, 1234);
+    assert_eq!(schedule.len(), 5);
+
+    for round in &schedule {
+        let mut sorted = round.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, vec![0, 1, 2, 3]);
+    }
+}
+
+#[test]
+fn test_splitmix64_is_deterministic_for_a_given_seed() {
+    let mut a = SplitMix64::new(7);
+    let mut b = SplitMix64::new(7);
+
+    for _ in 0..100 {
+        assert_eq!(a.next_u64(), b.next_u64());
+    }
+}
@@ -0,0 +1,17 @@
+use crate::util::units::Second;
+
+/// The result of running and measuring a single command invocation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TimingResult {
+    /// Wall clock time
+    pub time_real: Second,
+
+    /// User CPU time
+    pub time_user: Second,
+
+    /// System CPU time
+    pub time_system: Second,
+
+    /// Peak resident set size, in bytes, of the child process
+    pub memory_peak: u64,
+}
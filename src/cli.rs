@@ -0,0 +1,77 @@
+use clap::Parser;
+
+use crate::export::ExportType;
+
+/// A command-line benchmarking tool.
+#[derive(Parser, Debug)]
+pub struct Args {
+    /// The shell to use for executing benchmarked commands.
+    #[arg(long = "shell", short = 'S')]
+    pub shell: Option<String>,
+
+    /// Ignore non-zero exit codes of the benchmarked commands.
+    #[arg(long = "ignore-failure", short = 'i')]
+    pub ignore_failure: bool,
+
+    /// Export the results as a JUnit XML report to the given file.
+    #[arg(long = "export-junit")]
+    pub export_junit: Option<String>,
+
+    /// Export the results as a Markdown table to the given file.
+    #[arg(long = "export-markdown")]
+    pub export_markdown: Option<String>,
+
+    /// When exporting to JUnit, mark a command's `<testcase>` as failed if
+    /// it is slower than the fastest command by more than this factor.
+    #[arg(long = "junit-fail-slower-than")]
+    pub junit_fail_slower_than: Option<f64>,
+
+    /// Report a winsorized mean and MAD-based spread instead of the plain
+    /// mean and standard deviation in the Markdown export.
+    #[arg(long = "robust-stats")]
+    pub robust_stats: bool,
+
+    /// The fixed number of runs to perform per command. Ignored if
+    /// `--adaptive` is given.
+    #[arg(long = "runs", short = 'r')]
+    pub runs: Option<u64>,
+
+    /// Keep running each command until its relative standard error
+    /// (stderr / mean) drops below this fraction, e.g. `0.01` for 1%,
+    /// instead of running a fixed number of times.
+    #[arg(long = "adaptive")]
+    pub adaptive: Option<f64>,
+
+    /// The minimum number of runs per command, honored both in fixed and
+    /// `--adaptive` mode.
+    #[arg(long = "min-runs")]
+    pub min_runs: Option<u64>,
+
+    /// The maximum number of runs per command when `--adaptive` is given.
+    #[arg(long = "max-runs")]
+    pub max_runs: Option<u64>,
+
+    /// Interleave the runs of all benchmarked commands in a shuffled order
+    /// each round, instead of running all of one command's repetitions
+    /// before moving to the next. Takes an optional seed; if none is given,
+    /// a random seed is chosen and printed so the run can be reproduced.
+    #[arg(long = "shuffle-seed", num_args = 0..=1)]
+    pub shuffle_seed: Option<Option<u64>>,
+}
+
+impl Args {
+    /// The list of (export type, output path) pairs requested on the command line.
+    pub fn export_targets(&self) -> Vec<(ExportType, &str)> {
+        let mut targets = Vec::new();
+
+        if let Some(path) = &self.export_junit {
+            targets.push((ExportType::Junit, path.as_str()));
+        }
+
+        if let Some(path) = &self.export_markdown {
+            targets.push((ExportType::Markdown, path.as_str()));
+        }
+
+        targets
+    }
+}
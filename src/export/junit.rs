@@ -0,0 +1,222 @@
+use super::Exporter;
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::benchmark::relative_speed::{self, BenchmarkResultWithRelativeSpeed};
+use crate::util::units::Unit;
+
+use anyhow::{anyhow, Result};
+
+/// Exports JUnit XML, suitable for consumption by CI systems that already
+/// know how to parse `<testsuite>`/`<testcase>` reports.
+#[derive(Default)]
+pub struct JunitExporter {
+    /// If set, a command is reported as a `<failure>` when it is slower than
+    /// the fastest command by more than this factor, in addition to any
+    /// command whose runs produced a non-zero exit code.
+    pub fail_slower_than: Option<f64>,
+}
+
+impl Exporter for JunitExporter {
+    fn serialize(&self, results: &[BenchmarkResult], _unit: Option<Unit>) -> Result<Vec<u8>> {
+        if let Some(annotated_results) = relative_speed::compute(results) {
+            Ok(test_suite(&annotated_results, self.fail_slower_than).into_bytes())
+        } else {
+            Err(anyhow!(
+                "Relative speed comparison is not available for JUnit export."
+            ))
+        }
+    }
+}
+
+fn test_suite(
+    annotated_results: &[BenchmarkResultWithRelativeSpeed],
+    fail_slower_than: Option<f64>,
+) -> String {
+    let total_time: f64 = annotated_results.iter().map(|r| r.result.mean).sum();
+
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n\
+         <testsuite name=\"hyperfine\" tests=\"{tests}\" time=\"{time}\">\n",
+        tests = annotated_results.len(),
+        time = total_time,
+    );
+
+    for entry in annotated_results {
+        xml.push_str(&test_case(entry, fail_slower_than));
+    }
+
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+/// Escape the characters that are not valid inside an XML attribute value.
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn test_case(entry: &BenchmarkResultWithRelativeSpeed, fail_slower_than: Option<f64>) -> String {
+    let result = &entry.result;
+    let name = xml_escape(&result.command);
+
+    let failed_exit_codes: Vec<i32> = result
+        .exit_codes
+        .iter()
+        .filter_map(|code| *code)
+        .filter(|&code| code != 0)
+        .collect();
+
+    let is_too_slow = fail_slower_than
+        .map(|factor| entry.relative_speed > factor)
+        .unwrap_or(false);
+
+    if failed_exit_codes.is_empty() && !is_too_slow {
+        format!(
+            "  <testcase name=\"{name}\" time=\"{time}\" />\n",
+            name = name,
+            time = result.mean,
+        )
+    } else {
+        let message = xml_escape(&if !failed_exit_codes.is_empty() {
+            format!(
+                "Command exited with non-zero status codes: {:?}",
+                failed_exit_codes
+            )
+        } else {
+            format!(
+                "Command was {:.2}x slower than the fastest command (limit: {:.2}x)",
+                entry.relative_speed,
+                fail_slower_than.unwrap(),
+            )
+        });
+
+        format!(
+            "  <testcase name=\"{name}\" time=\"{time}\">\n    <failure message=\"{message}\" />\n  </testcase>\n",
+            name = name,
+            time = result.mean,
+            message = message,
+        )
+    }
+}
+
+#[test]
+fn test_xml_escape() {
+    assert_eq!(
+        xml_escape("make && make test < in.txt > out.txt \"quoted\""),
+        "make &amp;&amp; make test &lt; in.txt &gt; out.txt &quot;quoted&quot;"
+    );
+}
+
+#[test]
+fn test_junit_format_passing_command_escapes_name() {
+    use std::collections::BTreeMap;
+    let exporter = JunitExporter::default();
+
+    let timing_results = vec![BenchmarkResult {
+        command: String::from("echo 1 > out.txt && echo done"),
+        mean: 0.05,
+        stddev: Some(0.001),
+        median: 0.05,
+        user: 0.0009,
+        system: 0.0011,
+        min: 0.049,
+        max: 0.051,
+        times: Some(vec![0.05, 0.05]),
+        exit_codes: vec![Some(0), Some(0)],
+        parameters: BTreeMap::new(),
+        memory_peak: None,
+    }];
+
+    let formatted = String::from_utf8(exporter.serialize(&timing_results, None).unwrap()).unwrap();
+
+    let formatted_expected = "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n\
+         <testsuite name=\"hyperfine\" tests=\"1\" time=\"0.05\">\n\
+         \x20 <testcase name=\"echo 1 &gt; out.txt &amp;&amp; echo done\" time=\"0.05\" />\n\
+         </testsuite>\n";
+
+    assert_eq!(formatted_expected, formatted);
+}
+
+#[test]
+fn test_junit_format_nonzero_exit_code_reports_failure() {
+    use std::collections::BTreeMap;
+    let exporter = JunitExporter::default();
+
+    let timing_results = vec![BenchmarkResult {
+        command: String::from("false"),
+        mean: 0.001,
+        stddev: Some(0.0),
+        median: 0.001,
+        user: 0.0,
+        system: 0.0,
+        min: 0.001,
+        max: 0.001,
+        times: Some(vec![0.001, 0.001]),
+        exit_codes: vec![Some(1), Some(1)],
+        parameters: BTreeMap::new(),
+        memory_peak: None,
+    }];
+
+    let formatted = String::from_utf8(exporter.serialize(&timing_results, None).unwrap()).unwrap();
+
+    let formatted_expected = "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n\
+         <testsuite name=\"hyperfine\" tests=\"1\" time=\"0.001\">\n\
+         \x20 <testcase name=\"false\" time=\"0.001\">\n\
+         \x20   <failure message=\"Command exited with non-zero status codes: [1, 1]\" />\n\
+         \x20 </testcase>\n\
+         </testsuite>\n";
+
+    assert_eq!(formatted_expected, formatted);
+}
+
+#[test]
+fn test_junit_format_fail_slower_than_threshold() {
+    use std::collections::BTreeMap;
+    let exporter = JunitExporter {
+        fail_slower_than: Some(2.0),
+    };
+
+    let timing_results = vec![
+        BenchmarkResult {
+            command: String::from("fast"),
+            mean: 0.1,
+            stddev: Some(0.0),
+            median: 0.1,
+            user: 0.0,
+            system: 0.0,
+            min: 0.1,
+            max: 0.1,
+            times: Some(vec![0.1, 0.1]),
+            exit_codes: vec![Some(0), Some(0)],
+            parameters: BTreeMap::new(),
+            memory_peak: None,
+        },
+        BenchmarkResult {
+            command: String::from("slow"),
+            mean: 0.25,
+            stddev: Some(0.0),
+            median: 0.25,
+            user: 0.0,
+            system: 0.0,
+            min: 0.25,
+            max: 0.25,
+            times: Some(vec![0.25, 0.25]),
+            exit_codes: vec![Some(0), Some(0)],
+            parameters: BTreeMap::new(),
+            memory_peak: None,
+        },
+    ];
+
+    let formatted = String::from_utf8(exporter.serialize(&timing_results, None).unwrap()).unwrap();
+
+    let formatted_expected = "<?xml version=\"1.0\" encoding=\"UTF-8\" ?>\n\
+         <testsuite name=\"hyperfine\" tests=\"2\" time=\"0.35\">\n\
+         \x20 <testcase name=\"fast\" time=\"0.1\" />\n\
+         \x20 <testcase name=\"slow\" time=\"0.25\">\n\
+         \x20   <failure message=\"Command was 2.50x slower than the fastest command (limit: 2.00x)\" />\n\
+         \x20 </testcase>\n\
+         </testsuite>\n";
+
+    assert_eq!(formatted_expected, formatted);
+}
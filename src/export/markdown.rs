@@ -5,9 +5,26 @@ use crate::output::format::format_duration_value;
 use crate::util::units::Unit;
 
 use anyhow::{anyhow, Result};
+use statistical::mean;
+
+/// Scale factor that turns a MAD into an estimator comparable to the
+/// standard deviation under a normality assumption.
+const MAD_SCALE_FACTOR: f64 = 1.4826;
+
+/// Fraction of samples clamped at each end when computing the winsorized
+/// mean, e.g. 0.05 clamps the bottom and top 5% of samples.
+const WINSORIZE_PERCENTILE: f64 = 0.05;
+
+/// Ratio of stddev to scaled-MAD above which the two are considered to
+/// diverge sharply, hinting at heavy-tailed measurement noise.
+const ROBUSTNESS_DIVERGENCE_THRESHOLD: f64 = 1.5;
 
 #[derive(Default)]
-pub struct MarkdownExporter {}
+pub struct MarkdownExporter {
+    /// Report a winsorized mean and a MAD-based spread instead of the plain
+    /// mean and standard deviation.
+    pub robust_stats: bool,
+}
 
 impl Exporter for MarkdownExporter {
     fn serialize(&self, results: &[BenchmarkResult], unit: Option<Unit>) -> Result<Vec<u8>> {
@@ -23,10 +40,11 @@ impl Exporter for MarkdownExporter {
         };
 
         if let Some(annotated_results) = relative_speed::compute(results) {
-            let mut destination = start_table(unit);
+            let show_memory = results.iter().any(|r| r.memory_peak.is_some());
+            let mut destination = start_table(unit, self.robust_stats, show_memory);
 
             for result in annotated_results {
-                add_table_row(&mut destination, &result, unit);
+                add_table_row(&mut destination, &result, unit, self.robust_stats, show_memory);
             }
 
             Ok(destination)
@@ -38,25 +56,137 @@ impl Exporter for MarkdownExporter {
     }
 }
 
-fn table_header(unit_short_name: String) -> String {
+/// The winsorized mean of `times` at the given percentile `p` (0.0..0.5):
+/// samples below the p-th percentile are clamped up to it, and samples
+/// above the (1-p)-th percentile are clamped down to it, before averaging.
+///
+/// At hyperfine's typical run counts, `p` alone would trim nothing (e.g.
+/// `floor(10 * 0.05) == 0`), making this a no-op. To keep the statistic
+/// meaningful for small samples, at least the single most extreme sample on
+/// each side is always clamped when `n > 1`, capped so the two clamp points
+/// never cross.
+fn winsorized_mean(times: &[f64], p: f64) -> f64 {
+    if times.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted = times.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n = sorted.len();
+    let max_index = (n - 1) / 2;
+    let lower_index = if n > 1 {
+        (((n as f64) * p).floor() as usize).max(1).min(max_index)
+    } else {
+        0
+    };
+    let upper_index = (n - 1).saturating_sub(lower_index);
+
+    let lower_bound = sorted[lower_index];
+    let upper_bound = sorted[upper_index];
+
+    let clamped: Vec<f64> = sorted
+        .iter()
+        .map(|&x| x.clamp(lower_bound, upper_bound))
+        .collect();
+
+    mean(&clamped)
+}
+
+/// The median absolute deviation of `times`, scaled by [`MAD_SCALE_FACTOR`]
+/// so that it is comparable to a standard deviation under normality.
+fn median_absolute_deviation(times: &[f64]) -> f64 {
+    let m = median(times);
+    let deviations: Vec<f64> = times.iter().map(|&x| (x - m).abs()).collect();
+
+    median(&deviations) * MAD_SCALE_FACTOR
+}
+
+fn median(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let n = sorted.len();
+    if n % 2 == 0 {
+        (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+    } else {
+        sorted[n / 2]
+    }
+}
+
+fn table_header(unit_short_name: String, robust_stats: bool, show_memory: bool) -> String {
+    let mean_column = if robust_stats { "Winsorized Mean" } else { "Mean" };
+    let divergence_column = if robust_stats { " Divergence |" } else { "" };
+    let divergence_rule = if robust_stats { ":---:|" } else { "" };
+    let memory_column = if show_memory { " Peak Mem |" } else { "" };
+    let memory_rule = if show_memory { "---:|" } else { "" };
+
     format!(
-        "| Command | Mean [{unit}] | Min [{unit}] | Max [{unit}] | Relative |\n|:---|---:|---:|---:|---:|\n",
-        unit = unit_short_name
+        "| Command | {mean_column} [{unit}] | Min [{unit}] | Max [{unit}] |{divergence_column}{memory_column} Relative |\n\
+         |:---|---:|---:|---:|{divergence_rule}{memory_rule}---:|\n",
+        mean_column = mean_column,
+        unit = unit_short_name,
+        divergence_column = divergence_column,
+        divergence_rule = divergence_rule,
+        memory_column = memory_column,
+        memory_rule = memory_rule,
     )
 }
 
-fn start_table(unit: Unit) -> Vec<u8> {
-    table_header(unit.short_name()).bytes().collect()
+fn start_table(unit: Unit, robust_stats: bool, show_memory: bool) -> Vec<u8> {
+    table_header(unit.short_name(), robust_stats, show_memory)
+        .bytes()
+        .collect()
+}
+
+/// Format a byte count as a human-readable value in mebibytes.
+fn format_memory_value(bytes: u64) -> String {
+    format!("{:.1} MiB", (bytes as f64) / (1024.0 * 1024.0))
 }
 
-fn add_table_row(dest: &mut Vec<u8>, entry: &BenchmarkResultWithRelativeSpeed, unit: Unit) {
+fn add_table_row(
+    dest: &mut Vec<u8>,
+    entry: &BenchmarkResultWithRelativeSpeed,
+    unit: Unit,
+    robust_stats: bool,
+    show_memory: bool,
+) {
     let result = &entry.result;
-    let mean_str = format_duration_value(result.mean, Some(unit)).0;
-    let stddev_str = if let Some(stddev) = result.stddev {
-        format!(" ± {}", format_duration_value(stddev, Some(unit)).0)
+
+    let (mean_str, stddev_str, diverges) = if robust_stats {
+        if let Some(times) = result.times.as_ref() {
+            let w_mean = winsorized_mean(times, WINSORIZE_PERCENTILE);
+            let mad = median_absolute_deviation(times);
+            let diverges = matches!(
+                result.stddev,
+                Some(stddev) if mad > 0.0 && stddev / mad > ROBUSTNESS_DIVERGENCE_THRESHOLD
+            );
+
+            (
+                format_duration_value(w_mean, Some(unit)).0,
+                format!(" ± {}", format_duration_value(mad, Some(unit)).0),
+                diverges,
+            )
+        } else {
+            (
+                format_duration_value(result.mean, Some(unit)).0,
+                "".into(),
+                false,
+            )
+        }
     } else {
-        "".into()
+        let stddev_str = if let Some(stddev) = result.stddev {
+            format!(" ± {}", format_duration_value(stddev, Some(unit)).0)
+        } else {
+            "".into()
+        };
+        (format_duration_value(result.mean, Some(unit)).0, stddev_str, false)
     };
+
     let min_str = format_duration_value(result.min, Some(unit)).0;
     let max_str = format_duration_value(result.max, Some(unit)).0;
     let rel_str = format!("{:.2}", entry.relative_speed);
@@ -68,14 +198,34 @@ fn add_table_row(dest: &mut Vec<u8>, entry: &BenchmarkResultWithRelativeSpeed, u
         "".into()
     };
 
+    let divergence_str = if robust_stats {
+        format!(" {} |", if diverges { "!" } else { "" })
+    } else {
+        "".into()
+    };
+
+    let memory_str = if show_memory {
+        format!(
+            " {} |",
+            result
+                .memory_peak
+                .map(format_memory_value)
+                .unwrap_or_else(|| "-".into())
+        )
+    } else {
+        "".into()
+    };
+
     dest.extend(
         format!(
-            "| `{command}` | {mean}{stddev} | {min} | {max} | {rel}{rel_stddev} |\n",
+            "| `{command}` | {mean}{stddev} | {min} | {max} |{divergence}{memory} {rel}{rel_stddev} |\n",
             command = result.command.replace("|", "\\|"),
             mean = mean_str,
             stddev = stddev_str,
             min = min_str,
             max = max_str,
+            divergence = divergence_str,
+            memory = memory_str,
             rel = rel_str,
             rel_stddev = rel_stddev_str,
         )
@@ -107,6 +257,7 @@ fn test_markdown_format_ms() {
             times: Some(vec![0.1, 0.1, 0.1]),
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: BTreeMap::new(),
+            memory_peak: None,
         },
         BenchmarkResult {
             command: String::from("sleep 2"),
@@ -120,6 +271,7 @@ fn test_markdown_format_ms() {
             times: Some(vec![2.0, 2.0, 2.0]),
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: BTreeMap::new(),
+            memory_peak: None,
         },
     ];
 
@@ -130,7 +282,7 @@ fn test_markdown_format_ms() {
 | `sleep 0.1` | 105.7 ± 1.6 | 102.3 | 108.0 | 1.00 |
 | `sleep 2` | 2005.0 ± 2.0 | 2002.0 | 2008.0 | 18.97 ± 0.29 |
 ",
-        table_header("ms".to_string())
+        table_header("ms".to_string(), false, false)
     );
 
     assert_eq!(formatted_expected, formatted);
@@ -156,6 +308,7 @@ fn test_markdown_format_s() {
             times: Some(vec![2.0, 2.0, 2.0]),
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: BTreeMap::new(),
+            memory_peak: None,
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
@@ -169,6 +322,7 @@ fn test_markdown_format_s() {
             times: Some(vec![0.1, 0.1, 0.1]),
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: BTreeMap::new(),
+            memory_peak: None,
         },
     ];
 
@@ -179,7 +333,7 @@ fn test_markdown_format_s() {
 | `sleep 2` | 2.005 ± 0.002 | 2.002 | 2.008 | 18.97 ± 0.29 |
 | `sleep 0.1` | 0.106 ± 0.002 | 0.102 | 0.108 | 1.00 |
 ",
-        table_header("s".to_string())
+        table_header("s".to_string(), false, false)
     );
 
     assert_eq!(formatted_expected, formatted);
@@ -204,6 +358,7 @@ fn test_markdown_format_time_unit_s() {
             times: Some(vec![0.1, 0.1, 0.1]),
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: BTreeMap::new(),
+            memory_peak: None,
         },
         BenchmarkResult {
             command: String::from("sleep 2"),
@@ -217,6 +372,7 @@ fn test_markdown_format_time_unit_s() {
             times: Some(vec![2.0, 2.0, 2.0]),
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: BTreeMap::new(),
+            memory_peak: None,
         },
     ];
 
@@ -232,7 +388,7 @@ fn test_markdown_format_time_unit_s() {
 | `sleep 0.1` | 0.106 ± 0.002 | 0.102 | 0.108 | 1.00 |
 | `sleep 2` | 2.005 ± 0.002 | 2.002 | 2.008 | 18.97 ± 0.29 |
 ",
-        table_header("s".to_string())
+        table_header("s".to_string(), false, false)
     );
 
     assert_eq!(formatted_expected, formatted);
@@ -258,6 +414,7 @@ fn test_markdown_format_time_unit_ms() {
             times: Some(vec![2.0, 2.0, 2.0]),
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: BTreeMap::new(),
+            memory_peak: None,
         },
         BenchmarkResult {
             command: String::from("sleep 0.1"),
@@ -271,6 +428,7 @@ fn test_markdown_format_time_unit_ms() {
             times: Some(vec![0.1, 0.1, 0.1]),
             exit_codes: vec![Some(0), Some(0), Some(0)],
             parameters: BTreeMap::new(),
+            memory_peak: None,
         },
     ];
 
@@ -286,7 +444,111 @@ fn test_markdown_format_time_unit_ms() {
 | `sleep 2` | 2005.0 ± 2.0 | 2002.0 | 2008.0 | 18.97 ± 0.29 |
 | `sleep 0.1` | 105.7 ± 1.6 | 102.3 | 108.0 | 1.00 |
 ",
-        table_header("ms".to_string())
+        table_header("ms".to_string(), false, false)
+    );
+
+    assert_eq!(formatted_expected, formatted);
+}
+
+/// Samples below the 20th percentile and above the 80th percentile are
+/// clamped to those percentiles before averaging, so a single large outlier
+/// pulls the result far less than it would the plain mean.
+#[test]
+fn test_winsorized_mean_clamps_outliers() {
+    let times = vec![1.0, 2.0, 3.0, 4.0, 100.0];
+    assert_eq!(winsorized_mean(&times, 0.2), 3.0);
+}
+
+#[test]
+fn test_winsorized_mean_of_empty_slice_is_zero() {
+    assert_eq!(winsorized_mean(&[], 0.05), 0.0);
+}
+
+#[test]
+fn test_median_absolute_deviation() {
+    let times = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    // Median is 3.0; absolute deviations are [2, 1, 0, 1, 2], whose median
+    // is 1.0, scaled by MAD_SCALE_FACTOR.
+    assert_eq!(median_absolute_deviation(&times), MAD_SCALE_FACTOR);
+}
+
+/// With `robust_stats` enabled, the Mean column reports the winsorized mean
+/// and MAD instead of the plain mean/stddev, and a heavily outlier-skewed
+/// sample (where stddev diverges sharply from MAD) is flagged in the new
+/// Divergence column.
+#[test]
+fn test_markdown_format_robust_stats() {
+    use std::collections::BTreeMap;
+    let exporter = MarkdownExporter {
+        robust_stats: true,
+    };
+
+    let timing_results = vec![BenchmarkResult {
+        command: String::from("noisy-command"),
+        mean: 22.0,
+        stddev: Some(30.0),
+        median: 3.0,
+        user: 0.0009,
+        system: 0.0011,
+        min: 1.0,
+        max: 100.0,
+        times: Some(vec![1.0, 2.0, 3.0, 4.0, 100.0]),
+        exit_codes: vec![Some(0); 5],
+        parameters: BTreeMap::new(),
+        memory_peak: None,
+    }];
+
+    let formatted = String::from_utf8(
+        exporter
+            .serialize(&timing_results, Some(Unit::Second))
+            .unwrap(),
+    )
+    .unwrap();
+
+    let formatted_expected = format!(
+        "{}\
+| `noisy-command` | 3.000 ± 1.483 | 1.000 | 100.000 | ! | 1.00 |
+",
+        table_header("s".to_string(), true, false)
+    );
+
+    assert_eq!(formatted_expected, formatted);
+}
+
+/// When at least one result carries a peak memory measurement, a "Peak Mem"
+/// column is added to the table.
+#[test]
+fn test_markdown_format_with_memory() {
+    use std::collections::BTreeMap;
+    let exporter = MarkdownExporter::default();
+
+    let timing_results = vec![BenchmarkResult {
+        command: String::from("mem-command"),
+        mean: 1.0,
+        stddev: None,
+        median: 1.0,
+        user: 0.0,
+        system: 0.0,
+        min: 1.0,
+        max: 1.0,
+        times: None,
+        exit_codes: vec![Some(0)],
+        parameters: BTreeMap::new(),
+        memory_peak: Some(10 * 1024 * 1024),
+    }];
+
+    let formatted = String::from_utf8(
+        exporter
+            .serialize(&timing_results, Some(Unit::Second))
+            .unwrap(),
+    )
+    .unwrap();
+
+    let formatted_expected = format!(
+        "{}\
+| `mem-command` | 1.000 | 1.000 | 1.000 | 10.0 MiB | 1.00 |
+",
+        table_header("s".to_string(), false, true)
     );
 
     assert_eq!(formatted_expected, formatted);
@@ -0,0 +1,38 @@
+pub mod junit;
+pub mod markdown;
+
+use crate::benchmark::benchmark_result::BenchmarkResult;
+use crate::util::units::Unit;
+
+use anyhow::Result;
+
+use junit::JunitExporter;
+use markdown::MarkdownExporter;
+
+/// A type that can turn a set of benchmark results into a byte stream in
+/// some serialization format.
+pub trait Exporter {
+    fn serialize(&self, results: &[BenchmarkResult], unit: Option<Unit>) -> Result<Vec<u8>>;
+}
+
+/// The file format that a set of benchmark results should be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportType {
+    Markdown,
+    Junit,
+}
+
+/// Create the [`Exporter`] for the given `export_type`, wiring up any
+/// export-specific options (e.g. the JUnit slow-test threshold).
+pub fn build_exporter(
+    export_type: ExportType,
+    junit_fail_slower_than: Option<f64>,
+    robust_stats: bool,
+) -> Box<dyn Exporter> {
+    match export_type {
+        ExportType::Markdown => Box::new(MarkdownExporter { robust_stats }),
+        ExportType::Junit => Box::new(JunitExporter {
+            fail_slower_than: junit_fail_slower_than,
+        }),
+    }
+}
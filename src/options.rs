@@ -0,0 +1,107 @@
+use std::fmt;
+
+use crate::benchmark::{adaptive_options_from, DEFAULT_MAX_RUNS, DEFAULT_MIN_RUNS};
+use crate::benchmark::adaptive::AdaptiveRunsOptions;
+use crate::benchmark::scheduler;
+use crate::cli::Args;
+
+/// What to do when a benchmarked command exits with a non-zero status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CmdFailureAction {
+    RaiseError,
+    Ignore,
+}
+
+/// How to handle the stdout/stderr of the benchmarked commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOutputPolicy {
+    Discard,
+    Inherit,
+}
+
+impl CommandOutputPolicy {
+    pub fn get_stdout_stderr(&self) -> (std::process::Stdio, std::process::Stdio) {
+        match self {
+            CommandOutputPolicy::Discard => {
+                (std::process::Stdio::null(), std::process::Stdio::null())
+            }
+            CommandOutputPolicy::Inherit => {
+                (std::process::Stdio::inherit(), std::process::Stdio::inherit())
+            }
+        }
+    }
+}
+
+/// Whether progress output (spinners, progress bars) should be shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputStyleOption {
+    Full,
+    Disabled,
+}
+
+/// The shell used to run benchmarked commands.
+#[derive(Debug, Clone)]
+pub struct Shell(pub String);
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Shell {
+    pub fn command(&self) -> std::process::Command {
+        std::process::Command::new(&self.0)
+    }
+}
+
+/// Resolved runtime configuration, derived from [`Args`], that the
+/// benchmarking and execution code is parameterized over.
+pub struct Options {
+    pub command_failure_action: CmdFailureAction,
+    pub command_output_policy: CommandOutputPolicy,
+    pub output_style: OutputStyleOption,
+
+    /// See [`Args::junit_fail_slower_than`].
+    pub junit_fail_slower_than: Option<f64>,
+
+    /// The fixed number of runs to perform per command, when
+    /// `adaptive_runs` is not set.
+    pub runs: u64,
+
+    /// See [`Args::adaptive`]. `None` means a fixed `runs` count is used
+    /// instead of the confidence-interval stopping rule.
+    pub adaptive_runs: Option<AdaptiveRunsOptions>,
+
+    /// If set, runs are interleaved across commands in a shuffled order
+    /// seeded by this value. See [`Args::shuffle_seed`].
+    pub shuffle_seed: Option<u64>,
+}
+
+impl Options {
+    pub fn from_cli_arguments(args: &Args) -> Options {
+        Options {
+            command_failure_action: if args.ignore_failure {
+                CmdFailureAction::Ignore
+            } else {
+                CmdFailureAction::RaiseError
+            },
+            command_output_policy: CommandOutputPolicy::Discard,
+            output_style: OutputStyleOption::Full,
+            junit_fail_slower_than: args.junit_fail_slower_than,
+            runs: args
+                .runs
+                .unwrap_or_else(|| args.min_runs.unwrap_or(DEFAULT_MIN_RUNS)),
+            adaptive_runs: args.adaptive.map(|target_relative_stderr| {
+                adaptive_options_from(
+                    target_relative_stderr,
+                    args.min_runs.unwrap_or(DEFAULT_MIN_RUNS),
+                    args.max_runs.unwrap_or(DEFAULT_MAX_RUNS),
+                )
+            }),
+            shuffle_seed: args.shuffle_seed.map(|maybe_seed| {
+                maybe_seed.unwrap_or_else(scheduler::random_seed)
+            }),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::process::ExitStatus;
+
+use crate::util::units::Second;
+
+/// The result of spawning and waiting for a single child process.
+pub struct TimerResult {
+    pub time_real: Second,
+    pub time_user: Second,
+    pub time_system: Second,
+    /// Peak resident set size of the child, in bytes.
+    pub memory_peak: u64,
+    pub status: ExitStatus,
+}
+
+#[cfg(unix)]
+pub fn execute_and_measure(mut command: std::process::Command) -> std::io::Result<TimerResult> {
+    use std::time::Instant;
+
+    let start = Instant::now();
+    let child = command.spawn()?;
+    let (status, rusage) = wait4::wait4(child.id() as i32)?;
+    let time_real = start.elapsed().as_secs_f64();
+
+    let time_user = rusage.utime.as_secs_f64();
+    let time_system = rusage.stime.as_secs_f64();
+
+    // On Linux, `ru_maxrss` is reported in kilobytes; on macOS, in bytes.
+    #[cfg(target_os = "macos")]
+    let memory_peak = rusage.maxrss as u64;
+    #[cfg(not(target_os = "macos"))]
+    let memory_peak = rusage.maxrss as u64 * 1024;
+
+    Ok(TimerResult {
+        time_real,
+        time_user,
+        time_system,
+        memory_peak,
+        status,
+    })
+}
+
+#[cfg(windows)]
+pub fn execute_and_measure(mut command: std::process::Command) -> std::io::Result<TimerResult> {
+    use std::os::windows::io::AsRawHandle;
+    use std::time::Instant;
+    use winapi::shared::minwindef::FILETIME;
+    use winapi::um::processthreadsapi::GetProcessTimes;
+    use winapi::um::psapi::{GetProcessMemoryInfo, PROCESS_MEMORY_COUNTERS};
+
+    fn filetime_to_secs(ft: FILETIME) -> Second {
+        let ticks = ((ft.dwHighDateTime as u64) << 32) | (ft.dwLowDateTime as u64);
+        // FILETIME ticks are in units of 100ns.
+        ticks as f64 * 1e-7
+    }
+
+    let start = Instant::now();
+    let mut child = command.spawn()?;
+    let handle = child.as_raw_handle();
+    let status = child.wait()?;
+    let time_real = start.elapsed().as_secs_f64();
+
+    let (mut creation, mut exit, mut kernel, mut user) = unsafe { std::mem::zeroed() };
+    let (time_user, time_system) = unsafe {
+        if GetProcessTimes(handle as _, &mut creation, &mut exit, &mut kernel, &mut user) != 0 {
+            (filetime_to_secs(user), filetime_to_secs(kernel))
+        } else {
+            (0.0, 0.0)
+        }
+    };
+
+    let mut counters: PROCESS_MEMORY_COUNTERS = unsafe { std::mem::zeroed() };
+    let memory_peak = unsafe {
+        if GetProcessMemoryInfo(
+            handle as _,
+            &mut counters,
+            std::mem::size_of::<PROCESS_MEMORY_COUNTERS>() as u32,
+        ) != 0
+        {
+            counters.PeakWorkingSetSize as u64
+        } else {
+            0
+        }
+    };
+
+    Ok(TimerResult {
+        time_real,
+        time_user,
+        time_system,
+        memory_peak,
+        status,
+    })
+}